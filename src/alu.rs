@@ -0,0 +1,227 @@
+//! Arithmetic core shared by every opcode that touches the accumulator.
+//!
+//! The free `*_half_carry`/`*_carry` helpers compute the raw carry-out of an
+//! 8-bit or 16-bit add/sub without touching any register state, so they can be
+//! reused by both the `alu_*` routines below and, later, 16-bit INC/DEC and
+//! `ADD HL,rr` handlers. The `alu_*` routines are the ones opcode handlers
+//! actually call: they read and write `Registers::A` and set all four flags.
+
+use crate::registers::{FlagRegister, Register, Registers};
+
+/// add_half_carry reports whether `a + b` carries out of bit 3 (the low nibble).
+pub fn add_half_carry(a: u8, b: u8) -> bool {
+    (a & 0x0F) + (b & 0x0F) > 0x0F
+}
+
+/// sub_half_carry reports whether `a - b` borrows out of bit 4 (the low nibble).
+pub fn sub_half_carry(a: u8, b: u8) -> bool {
+    (a & 0x0F) < (b & 0x0F)
+}
+
+/// add_half_carry_16 reports whether `a + b` carries out of bit 11, the
+/// 16-bit equivalent of `add_half_carry` used by `ADD HL,rr`.
+pub fn add_half_carry_16(a: u16, b: u16) -> bool {
+    (a & 0x0FFF) + (b & 0x0FFF) > 0x0FFF
+}
+
+/// sub_half_carry_16 reports whether `a - b` borrows out of bit 12.
+pub fn sub_half_carry_16(a: u16, b: u16) -> bool {
+    (a & 0x0FFF) < (b & 0x0FFF)
+}
+
+/// add_carry_16 reports whether `a + b` overflows 16 bits.
+pub fn add_carry_16(a: u16, b: u16) -> bool {
+    (a as u32) + (b as u32) > 0xFFFF
+}
+
+/// sub_carry_16 reports whether `a - b` underflows 16 bits.
+pub fn sub_carry_16(a: u16, b: u16) -> bool {
+    b > a
+}
+
+/// alu_add computes `A + value`, stores the result in A, and sets Zero,
+/// Subtract (always false), HalfCarry, and Carry from the addition.
+pub fn alu_add(regs: &mut Registers, value: u8) {
+    let a = regs.get_8bit(Register::A);
+    let (result, carry) = a.overflowing_add(value);
+
+    regs.set_flag(FlagRegister::Zero, result == 0);
+    regs.set_flag(FlagRegister::Subtract, false);
+    regs.set_flag(FlagRegister::HalfCarry, add_half_carry(a, value));
+    regs.set_flag(FlagRegister::Carry, carry);
+    regs.set(Register::A, result as u16);
+}
+
+/// alu_adc computes `A + value + Carry`, folding the existing carry flag into
+/// the addend before computing half-carry/carry, then stores the result in A.
+pub fn alu_adc(regs: &mut Registers, value: u8) {
+    let a = regs.get_8bit(Register::A);
+    let carry_in = regs.get_flag(FlagRegister::Carry) as u8;
+    let result = a.wrapping_add(value).wrapping_add(carry_in);
+    let half_carry = (a & 0x0F) + (value & 0x0F) + carry_in > 0x0F;
+    let carry = (a as u16) + (value as u16) + (carry_in as u16) > 0xFF;
+
+    regs.set_flag(FlagRegister::Zero, result == 0);
+    regs.set_flag(FlagRegister::Subtract, false);
+    regs.set_flag(FlagRegister::HalfCarry, half_carry);
+    regs.set_flag(FlagRegister::Carry, carry);
+    regs.set(Register::A, result as u16);
+}
+
+/// alu_sub computes `A - value`, stores the result in A, and sets Zero,
+/// Subtract (always true), HalfCarry, and Carry from the subtraction.
+pub fn alu_sub(regs: &mut Registers, value: u8) {
+    let a = regs.get_8bit(Register::A);
+    let (result, carry) = a.overflowing_sub(value);
+
+    regs.set_flag(FlagRegister::Zero, result == 0);
+    regs.set_flag(FlagRegister::Subtract, true);
+    regs.set_flag(FlagRegister::HalfCarry, sub_half_carry(a, value));
+    regs.set_flag(FlagRegister::Carry, carry);
+    regs.set(Register::A, result as u16);
+}
+
+/// alu_sbc computes `A - value - Carry`, folding the existing carry flag into
+/// the subtrahend before computing half-carry/carry, then stores the result in A.
+pub fn alu_sbc(regs: &mut Registers, value: u8) {
+    let a = regs.get_8bit(Register::A);
+    let carry_in = regs.get_flag(FlagRegister::Carry) as u8;
+    let result = a.wrapping_sub(value).wrapping_sub(carry_in);
+    let half_carry = (a & 0x0F) < (value & 0x0F) + carry_in;
+    let carry = (a as i16) - (value as i16) - (carry_in as i16) < 0;
+
+    regs.set_flag(FlagRegister::Zero, result == 0);
+    regs.set_flag(FlagRegister::Subtract, true);
+    regs.set_flag(FlagRegister::HalfCarry, half_carry);
+    regs.set_flag(FlagRegister::Carry, carry);
+    regs.set(Register::A, result as u16);
+}
+
+/// alu_cp compares `A` against `value` like `alu_sub`, setting the same
+/// flags, but discards the result and leaves A untouched.
+pub fn alu_cp(regs: &mut Registers, value: u8) {
+    let a = regs.get_8bit(Register::A);
+
+    regs.set_flag(FlagRegister::Zero, a == value);
+    regs.set_flag(FlagRegister::Subtract, true);
+    regs.set_flag(FlagRegister::HalfCarry, sub_half_carry(a, value));
+    regs.set_flag(FlagRegister::Carry, a < value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_half_carry() {
+        assert!(add_half_carry(0x0F, 0x01));
+        assert!(!add_half_carry(0x0E, 0x01));
+    }
+
+    #[test]
+    fn test_sub_half_carry() {
+        assert!(sub_half_carry(0x10, 0x01));
+        assert!(!sub_half_carry(0x11, 0x01));
+    }
+
+    #[test]
+    fn test_add_half_carry_16() {
+        assert!(add_half_carry_16(0x0FFF, 0x0001));
+        assert!(!add_half_carry_16(0x0FFE, 0x0001));
+    }
+
+    #[test]
+    fn test_add_carry_16() {
+        assert!(add_carry_16(0xFFFF, 0x0001));
+        assert!(!add_carry_16(0xFFFE, 0x0001));
+    }
+
+    #[test]
+    fn test_alu_add_sets_result_and_flags() {
+        let mut regs = Registers::new();
+        regs.set(Register::A, 0x3A);
+        alu_add(&mut regs, 0xC6);
+        assert_eq!(regs.get_8bit(Register::A), 0x00);
+        assert!(regs.get_flag(FlagRegister::Zero));
+        assert!(!regs.get_flag(FlagRegister::Subtract));
+        assert!(regs.get_flag(FlagRegister::HalfCarry));
+        assert!(regs.get_flag(FlagRegister::Carry));
+    }
+
+    #[test]
+    fn test_alu_add_no_carry() {
+        let mut regs = Registers::new();
+        regs.set(Register::A, 0x01);
+        alu_add(&mut regs, 0x01);
+        assert_eq!(regs.get_8bit(Register::A), 0x02);
+        assert!(!regs.get_flag(FlagRegister::Zero));
+        assert!(!regs.get_flag(FlagRegister::HalfCarry));
+        assert!(!regs.get_flag(FlagRegister::Carry));
+    }
+
+    #[test]
+    fn test_alu_adc_folds_carry_in() {
+        let mut regs = Registers::new();
+        regs.set(Register::A, 0x0F);
+        regs.set_flag(FlagRegister::Carry, true);
+        alu_adc(&mut regs, 0x00);
+        assert_eq!(regs.get_8bit(Register::A), 0x10);
+        assert!(regs.get_flag(FlagRegister::HalfCarry));
+        assert!(!regs.get_flag(FlagRegister::Carry));
+    }
+
+    #[test]
+    fn test_alu_sub_sets_result_and_flags() {
+        let mut regs = Registers::new();
+        regs.set(Register::A, 0x3E);
+        alu_sub(&mut regs, 0x3E);
+        assert_eq!(regs.get_8bit(Register::A), 0x00);
+        assert!(regs.get_flag(FlagRegister::Zero));
+        assert!(regs.get_flag(FlagRegister::Subtract));
+        assert!(!regs.get_flag(FlagRegister::HalfCarry));
+        assert!(!regs.get_flag(FlagRegister::Carry));
+    }
+
+    #[test]
+    fn test_alu_sub_borrows() {
+        let mut regs = Registers::new();
+        regs.set(Register::A, 0x00);
+        alu_sub(&mut regs, 0x01);
+        assert_eq!(regs.get_8bit(Register::A), 0xFF);
+        assert!(regs.get_flag(FlagRegister::Subtract));
+        assert!(regs.get_flag(FlagRegister::HalfCarry));
+        assert!(regs.get_flag(FlagRegister::Carry));
+    }
+
+    #[test]
+    fn test_alu_sbc_folds_carry_in() {
+        let mut regs = Registers::new();
+        regs.set(Register::A, 0x10);
+        regs.set_flag(FlagRegister::Carry, true);
+        alu_sbc(&mut regs, 0x01);
+        assert_eq!(regs.get_8bit(Register::A), 0x0E);
+        assert!(regs.get_flag(FlagRegister::HalfCarry));
+        assert!(!regs.get_flag(FlagRegister::Carry));
+    }
+
+    #[test]
+    fn test_alu_cp_sets_flags_without_changing_a() {
+        let mut regs = Registers::new();
+        regs.set(Register::A, 0x3C);
+        alu_cp(&mut regs, 0x3C);
+        assert_eq!(regs.get_8bit(Register::A), 0x3C);
+        assert!(regs.get_flag(FlagRegister::Zero));
+        assert!(regs.get_flag(FlagRegister::Subtract));
+        assert!(!regs.get_flag(FlagRegister::Carry));
+    }
+
+    #[test]
+    fn test_alu_cp_sets_carry_when_a_less_than_value() {
+        let mut regs = Registers::new();
+        regs.set(Register::A, 0x02);
+        alu_cp(&mut regs, 0x05);
+        assert_eq!(regs.get_8bit(Register::A), 0x02);
+        assert!(!regs.get_flag(FlagRegister::Zero));
+        assert!(regs.get_flag(FlagRegister::Carry));
+    }
+}
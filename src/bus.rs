@@ -0,0 +1,241 @@
+//! Memory bus (MMU) wiring the CPU's 16-bit address space to the regions that
+//! back it: ROM (via a pluggable cartridge reader), VRAM, work RAM, OAM, I/O
+//! registers, HRAM, and the Interrupt Enable register. This is the other half
+//! of the CPU: `Registers` alone has nowhere to fetch opcodes or operands from.
+
+/// CartridgeReader is implemented by whatever backs ROM: a plain byte slice
+/// for a no-MBC cartridge today, and eventually one implementation per
+/// mapper chip (MBC1, MBC3, ...). The bus defers to it for both ROM regions.
+pub trait CartridgeReader {
+    fn read(&self, address: u16) -> u8;
+}
+
+/// A cartridge reader that always returns 0xFF, used when no cartridge is
+/// loaded so ROM reads behave like real open-bus hardware rather than
+/// silently returning zero.
+pub struct NoCartridge;
+
+impl CartridgeReader for NoCartridge {
+    fn read(&self, _address: u16) -> u8 {
+        0xFF
+    }
+}
+
+/// Region identifies which part of the address space a given address falls
+/// into, per the DMG memory map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    /// 0x0000-0x3FFF: fixed ROM bank 0.
+    RomBank0,
+    /// 0x4000-0x7FFF: switchable ROM bank.
+    RomBankN,
+    /// 0x8000-0x9FFF: video RAM.
+    VRam,
+    /// 0xA000-0xBFFF: external (cartridge) RAM.
+    ExternalRam,
+    /// 0xC000-0xDFFF: work RAM.
+    WorkRam,
+    /// 0xE000-0xFDFF: echo RAM, mirrors work RAM.
+    EchoRam,
+    /// 0xFE00-0xFE9F: object attribute memory (sprite table).
+    Oam,
+    /// 0xFEA0-0xFEFF: unusable, reads typically return 0xFF.
+    Unusable,
+    /// 0xFF00-0xFF7F: I/O registers.
+    IoRegisters,
+    /// 0xFF80-0xFFFE: high RAM.
+    HRam,
+    /// 0xFFFF: the Interrupt Enable register.
+    InterruptEnable,
+}
+
+/// MemoryMap classifies addresses into `Region`s. It holds no state; it's
+/// just the address-range table pulled out of `Bus` so the partitioning can
+/// be reasoned about (and tested) on its own.
+pub struct MemoryMap;
+
+impl MemoryMap {
+    pub fn get_map(address: u16) -> Region {
+        match address {
+            0x0000..=0x3FFF => Region::RomBank0,
+            0x4000..=0x7FFF => Region::RomBankN,
+            0x8000..=0x9FFF => Region::VRam,
+            0xA000..=0xBFFF => Region::ExternalRam,
+            0xC000..=0xDFFF => Region::WorkRam,
+            0xE000..=0xFDFF => Region::EchoRam,
+            0xFE00..=0xFE9F => Region::Oam,
+            0xFEA0..=0xFEFF => Region::Unusable,
+            0xFF00..=0xFF7F => Region::IoRegisters,
+            0xFF80..=0xFFFE => Region::HRam,
+            0xFFFF => Region::InterruptEnable,
+        }
+    }
+}
+
+/// Bus is the CPU's view of memory: a flat 64KB array for everything that
+/// isn't ROM, plus a pluggable `CartridgeReader` for the two ROM regions.
+pub struct Bus {
+    memory: [u8; 0x10000],
+    cartridge: Box<dyn CartridgeReader>,
+}
+
+impl Bus {
+    /// new creates a bus with zeroed RAM and no cartridge loaded (ROM reads
+    /// return 0xFF, matching open-bus hardware behavior).
+    pub fn new() -> Self {
+        Bus {
+            memory: [0; 0x10000],
+            cartridge: Box::new(NoCartridge),
+        }
+    }
+
+    /// with_cartridge creates a bus backed by the given cartridge reader for
+    /// ROM reads.
+    pub fn with_cartridge(cartridge: Box<dyn CartridgeReader>) -> Self {
+        Bus {
+            memory: [0; 0x10000],
+            cartridge,
+        }
+    }
+
+    /// read returns the byte at `address`, routing ROM regions to the
+    /// cartridge reader and mirroring echo RAM onto work RAM.
+    pub fn read(&self, address: u16) -> u8 {
+        match MemoryMap::get_map(address) {
+            Region::RomBank0 | Region::RomBankN => self.cartridge.read(address),
+            Region::EchoRam => self.memory[(address - 0x2000) as usize],
+            Region::Unusable => 0xFF,
+            _ => self.memory[address as usize],
+        }
+    }
+
+    /// write stores `value` at `address`. Writes to ROM regions are ignored
+    /// (real hardware would route these to MBC control registers once a
+    /// mapper is wired in); writes to echo RAM are mirrored onto work RAM.
+    pub fn write(&mut self, address: u16, value: u8) {
+        match MemoryMap::get_map(address) {
+            Region::RomBank0 | Region::RomBankN | Region::Unusable => {}
+            Region::EchoRam => self.memory[(address - 0x2000) as usize] = value,
+            _ => self.memory[address as usize] = value,
+        }
+    }
+
+    /// read_16bit reads a little-endian 16-bit value starting at `address`.
+    pub fn read_16bit(&self, address: u16) -> u16 {
+        let low = self.read(address) as u16;
+        let high = self.read(address.wrapping_add(1)) as u16;
+        (high << 8) | low
+    }
+
+    /// write_16bit stores `value` as a little-endian 16-bit value starting
+    /// at `address`.
+    pub fn write_16bit(&mut self, address: u16, value: u16) {
+        self.write(address, value as u8);
+        self.write(address.wrapping_add(1), (value >> 8) as u8);
+    }
+}
+
+impl Default for Bus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_map_regions() {
+        assert_eq!(MemoryMap::get_map(0x0000), Region::RomBank0);
+        assert_eq!(MemoryMap::get_map(0x3FFF), Region::RomBank0);
+        assert_eq!(MemoryMap::get_map(0x4000), Region::RomBankN);
+        assert_eq!(MemoryMap::get_map(0x7FFF), Region::RomBankN);
+        assert_eq!(MemoryMap::get_map(0x8000), Region::VRam);
+        assert_eq!(MemoryMap::get_map(0x9FFF), Region::VRam);
+        assert_eq!(MemoryMap::get_map(0xA000), Region::ExternalRam);
+        assert_eq!(MemoryMap::get_map(0xBFFF), Region::ExternalRam);
+        assert_eq!(MemoryMap::get_map(0xC000), Region::WorkRam);
+        assert_eq!(MemoryMap::get_map(0xDFFF), Region::WorkRam);
+        assert_eq!(MemoryMap::get_map(0xE000), Region::EchoRam);
+        assert_eq!(MemoryMap::get_map(0xFDFF), Region::EchoRam);
+        assert_eq!(MemoryMap::get_map(0xFE00), Region::Oam);
+        assert_eq!(MemoryMap::get_map(0xFE9F), Region::Oam);
+        assert_eq!(MemoryMap::get_map(0xFEA0), Region::Unusable);
+        assert_eq!(MemoryMap::get_map(0xFEFF), Region::Unusable);
+        assert_eq!(MemoryMap::get_map(0xFF00), Region::IoRegisters);
+        assert_eq!(MemoryMap::get_map(0xFF7F), Region::IoRegisters);
+        assert_eq!(MemoryMap::get_map(0xFF80), Region::HRam);
+        assert_eq!(MemoryMap::get_map(0xFFFE), Region::HRam);
+        assert_eq!(MemoryMap::get_map(0xFFFF), Region::InterruptEnable);
+    }
+
+    #[test]
+    fn test_read_write_work_ram() {
+        let mut bus = Bus::new();
+        bus.write(0xC000, 0x42);
+        assert_eq!(bus.read(0xC000), 0x42);
+    }
+
+    #[test]
+    fn test_echo_ram_mirrors_work_ram() {
+        let mut bus = Bus::new();
+        bus.write(0xC010, 0x7F);
+        assert_eq!(bus.read(0xE010), 0x7F);
+
+        bus.write(0xE020, 0x11);
+        assert_eq!(bus.read(0xC020), 0x11);
+    }
+
+    #[test]
+    fn test_unusable_region_reads_as_ff() {
+        let bus = Bus::new();
+        assert_eq!(bus.read(0xFEA0), 0xFF);
+    }
+
+    #[test]
+    fn test_rom_writes_are_ignored() {
+        let mut bus = Bus::new();
+        bus.write(0x0000, 0x42);
+        assert_eq!(bus.read(0x0000), 0xFF); // no cartridge loaded, open bus
+    }
+
+    #[test]
+    fn test_no_cartridge_rom_reads_open_bus() {
+        let bus = Bus::new();
+        assert_eq!(bus.read(0x0000), 0xFF);
+        assert_eq!(bus.read(0x7FFF), 0xFF);
+    }
+
+    struct FixedCartridge(Vec<u8>);
+
+    impl CartridgeReader for FixedCartridge {
+        fn read(&self, address: u16) -> u8 {
+            self.0[address as usize]
+        }
+    }
+
+    #[test]
+    fn test_cartridge_backed_rom_reads() {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0150] = 0xAB;
+        let bus = Bus::with_cartridge(Box::new(FixedCartridge(rom)));
+        assert_eq!(bus.read(0x0150), 0xAB);
+    }
+
+    #[test]
+    fn test_read_write_16bit_little_endian() {
+        let mut bus = Bus::new();
+        bus.write_16bit(0xC000, 0xBEEF);
+        assert_eq!(bus.read(0xC000), 0xEF);
+        assert_eq!(bus.read(0xC001), 0xBE);
+        assert_eq!(bus.read_16bit(0xC000), 0xBEEF);
+    }
+
+    #[test]
+    fn test_interrupt_enable_register_address() {
+        let mut bus = Bus::new();
+        bus.write(0xFFFF, 0x1F);
+        assert_eq!(bus.read(0xFFFF), 0x1F);
+    }
+}
@@ -0,0 +1,132 @@
+//! The 0xCB-prefixed opcode table: bit, rotate, and shift instructions. This
+//! is decoded as its own pass, separate from `opcode::decode`, since every
+//! CB opcode shares one byte layout (`op:2 bits | group:3 bits | operand:3
+//! bits`) that has nothing in common with the main table's addressing modes.
+
+/// CbOperand identifies which of the eight operand slots a CB opcode's low 3
+/// bits select. `HLIndirect` is its own variant (not `Register::HL`) because
+/// a CB op on slot 6 reads/writes the byte at the address in HL, not the
+/// 16-bit HL pair itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CbOperand {
+    B,
+    C,
+    D,
+    E,
+    H,
+    L,
+    HLIndirect,
+    A,
+}
+
+impl CbOperand {
+    fn from_bits(bits: u8) -> Self {
+        match bits & 0x07 {
+            0 => CbOperand::B,
+            1 => CbOperand::C,
+            2 => CbOperand::D,
+            3 => CbOperand::E,
+            4 => CbOperand::H,
+            5 => CbOperand::L,
+            6 => CbOperand::HLIndirect,
+            7 => CbOperand::A,
+            _ => unreachable!("bits & 0x07 is always in 0..=7"),
+        }
+    }
+}
+
+/// CbOp is the operation a CB opcode performs on its operand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CbOp {
+    /// Rotate left, bit 7 copied into both bit 0 and Carry.
+    Rlc,
+    /// Rotate right, bit 0 copied into both bit 7 and Carry.
+    Rrc,
+    /// Rotate left through Carry: old Carry becomes bit 0, old bit 7 becomes Carry.
+    Rl,
+    /// Rotate right through Carry: old Carry becomes bit 7, old bit 0 becomes Carry.
+    Rr,
+    /// Shift left, bit 0 filled with 0.
+    Sla,
+    /// Shift right, bit 7 preserved (arithmetic shift).
+    Sra,
+    /// Exchange the high and low nibbles.
+    Swap,
+    /// Shift right, bit 7 filled with 0.
+    Srl,
+    /// Test bit `b`, setting Zero to its complement.
+    Bit(u8),
+    /// Clear bit `b`.
+    Res(u8),
+    /// Set bit `b`.
+    Set(u8),
+}
+
+/// decode_cb maps a CB-prefixed opcode byte to its `CbOp`/`CbOperand` pair.
+/// Unlike `opcode::decode`, every byte 0x00-0xFF is a valid CB instruction,
+/// so this never needs to report "unimplemented".
+pub fn decode_cb(byte: u8) -> (CbOp, CbOperand) {
+    let operand = CbOperand::from_bits(byte);
+    let group = byte >> 6;
+    let selector = (byte >> 3) & 0x07;
+
+    let op = match group {
+        0 => match selector {
+            0 => CbOp::Rlc,
+            1 => CbOp::Rrc,
+            2 => CbOp::Rl,
+            3 => CbOp::Rr,
+            4 => CbOp::Sla,
+            5 => CbOp::Sra,
+            6 => CbOp::Swap,
+            7 => CbOp::Srl,
+            _ => unreachable!("selector is always in 0..=7"),
+        },
+        1 => CbOp::Bit(selector),
+        2 => CbOp::Res(selector),
+        3 => CbOp::Set(selector),
+        _ => unreachable!("byte >> 6 is always in 0..=3"),
+    };
+
+    (op, operand)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_rlc_b() {
+        assert_eq!(decode_cb(0x00), (CbOp::Rlc, CbOperand::B));
+    }
+
+    #[test]
+    fn test_decode_rrc_a() {
+        assert_eq!(decode_cb(0x0F), (CbOp::Rrc, CbOperand::A));
+    }
+
+    #[test]
+    fn test_decode_rl_through_srl() {
+        assert_eq!(decode_cb(0x10), (CbOp::Rl, CbOperand::B));
+        assert_eq!(decode_cb(0x18), (CbOp::Rr, CbOperand::B));
+        assert_eq!(decode_cb(0x20), (CbOp::Sla, CbOperand::B));
+        assert_eq!(decode_cb(0x28), (CbOp::Sra, CbOperand::B));
+        assert_eq!(decode_cb(0x30), (CbOp::Swap, CbOperand::B));
+        assert_eq!(decode_cb(0x38), (CbOp::Srl, CbOperand::B));
+    }
+
+    #[test]
+    fn test_decode_hl_indirect_operand() {
+        assert_eq!(decode_cb(0x06), (CbOp::Rlc, CbOperand::HLIndirect));
+    }
+
+    #[test]
+    fn test_decode_bit_res_set() {
+        assert_eq!(decode_cb(0x40), (CbOp::Bit(0), CbOperand::B));
+        assert_eq!(decode_cb(0x7F), (CbOp::Bit(7), CbOperand::A));
+        assert_eq!(decode_cb(0x80), (CbOp::Res(0), CbOperand::B));
+        assert_eq!(decode_cb(0xBF), (CbOp::Res(7), CbOperand::A));
+        assert_eq!(decode_cb(0xC0), (CbOp::Set(0), CbOperand::B));
+        assert_eq!(decode_cb(0xFF), (CbOp::Set(7), CbOperand::A));
+    }
+}
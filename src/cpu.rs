@@ -0,0 +1,828 @@
+//! The CPU interpreter: fetch/decode/execute loop tying `Registers`, `Bus`,
+//! and the opcode table together.
+
+use crate::alu;
+use crate::bus::Bus;
+use crate::cb::{decode_cb, CbOp, CbOperand};
+use crate::interrupts::Interrupt;
+use crate::opcode::{decode, Opcode, OpcodeParameter};
+use crate::registers::{FlagRegister, Register, Registers};
+
+/// Cpu owns the register file and the memory bus and steps a program one
+/// instruction at a time.
+pub struct Cpu {
+    pub registers: Registers,
+    pub bus: Bus,
+    /// Interrupt master enable. Gates whether a pending, IE-enabled
+    /// interrupt is serviced.
+    ime: bool,
+    /// Set by EI; takes effect at the end of the instruction *following*
+    /// EI, modeling the real one-instruction enable delay.
+    ime_scheduled: bool,
+    /// Set by HALT; cleared once an enabled interrupt becomes pending.
+    halted: bool,
+}
+
+impl Cpu {
+    pub fn new() -> Self {
+        Cpu {
+            registers: Registers::new(),
+            bus: Bus::new(),
+            ime: false,
+            ime_scheduled: false,
+            halted: false,
+        }
+    }
+
+    /// post_boot creates a CPU with registers in the DMG post-boot-ROM
+    /// state (see `Registers::post_boot`), for running a ROM without
+    /// executing the boot ROM first.
+    pub fn post_boot() -> Self {
+        Cpu {
+            registers: Registers::post_boot(),
+            ..Cpu::new()
+        }
+    }
+
+    /// fetch_byte reads the byte at PC and advances PC past it. Every read
+    /// of an opcode or an immediate operand goes through this, so PC always
+    /// ends an instruction pointing just past it.
+    fn fetch_byte(&mut self) -> u8 {
+        let byte = self.bus.read(self.registers.pc());
+        self.registers.set_pc(self.registers.pc().wrapping_add(1));
+        byte
+    }
+
+    /// fetch_word reads a little-endian 16-bit immediate at PC, advancing PC
+    /// past both bytes.
+    fn fetch_word(&mut self) -> u16 {
+        let low = self.fetch_byte() as u16;
+        let high = self.fetch_byte() as u16;
+        (high << 8) | low
+    }
+
+    /// fetch_i8 reads a signed 8-bit immediate at PC, advancing PC past it.
+    fn fetch_i8(&mut self) -> i8 {
+        self.fetch_byte() as i8
+    }
+
+    fn push(&mut self, value: u16) {
+        let sp = self.registers.sp().wrapping_sub(2);
+        self.registers.set_sp(sp);
+        self.bus.write_16bit(sp, value);
+    }
+
+    fn pop(&mut self) -> u16 {
+        let sp = self.registers.sp();
+        let value = self.bus.read_16bit(sp);
+        self.registers.set_sp(sp.wrapping_add(2));
+        value
+    }
+
+    /// step services a pending interrupt if one is enabled and IME is set;
+    /// otherwise, if halted, waits for one to become pending; otherwise
+    /// fetches, decodes, and executes a single instruction (including the
+    /// 0xCB-prefixed bit/rotate/shift table).
+    pub fn step(&mut self) {
+        if self.service_interrupts() {
+            return;
+        }
+
+        if self.halted {
+            if self.has_pending_interrupt() {
+                self.halted = false;
+            }
+            return;
+        }
+
+        // EI's delayed enable: capture whatever was scheduled *before* this
+        // instruction runs, so EI itself doesn't enable IME for its own step.
+        let enable_ime_after = self.ime_scheduled;
+        self.ime_scheduled = false;
+
+        let byte = self.fetch_byte();
+        if byte == 0xCB {
+            let cb_byte = self.fetch_byte();
+            let (op, operand) = decode_cb(cb_byte);
+            self.execute_cb(op, operand);
+        } else {
+            let (opcode, parameter) = decode(byte)
+                .unwrap_or_else(|| panic!("unimplemented opcode 0x{:02X}", byte));
+            self.execute(opcode, parameter);
+        }
+
+        if enable_ime_after {
+            self.ime = true;
+        }
+    }
+
+    /// service_interrupts jumps to the highest-priority pending, IE-enabled
+    /// interrupt's vector if IME is set, clearing IME and the serviced IF
+    /// bit, waking the CPU from HALT, and pushing the current PC as the
+    /// return address. Returns whether an interrupt was serviced.
+    fn service_interrupts(&mut self) -> bool {
+        if !self.ime {
+            return false;
+        }
+
+        for interrupt in Interrupt::ALL {
+            if self.bus.interrupt_enabled(interrupt) && self.bus.interrupt_requested(interrupt) {
+                self.ime = false;
+                self.bus.clear_interrupt(interrupt);
+                self.halted = false;
+                let pc = self.registers.pc();
+                self.push(pc);
+                self.registers.set_pc(interrupt.vector());
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// has_pending_interrupt reports whether any IE-enabled interrupt is
+    /// currently requested, regardless of IME. HALT wakes on this even
+    /// while interrupts are globally disabled.
+    fn has_pending_interrupt(&self) -> bool {
+        Interrupt::ALL
+            .iter()
+            .any(|i| self.bus.interrupt_enabled(*i) && self.bus.interrupt_requested(*i))
+    }
+
+    fn execute(&mut self, opcode: Opcode, parameter: OpcodeParameter) {
+        match (opcode, parameter) {
+            (Opcode::Nop, OpcodeParameter::None) => {}
+
+            (Opcode::Ld, OpcodeParameter::Register_U8(dst)) => {
+                let value = self.fetch_byte();
+                self.registers.set(dst, value as u16);
+            }
+            (Opcode::Ld, OpcodeParameter::Register_U16(dst)) => {
+                let value = self.fetch_word();
+                self.registers.set(dst, value);
+            }
+            (Opcode::Ld, OpcodeParameter::Register_U16Indirect(dst)) => {
+                let address = self.fetch_word();
+                self.registers.set(dst, self.bus.read(address) as u16);
+            }
+            (Opcode::Ld, OpcodeParameter::U16_Register(src)) => {
+                let address = self.fetch_word();
+                if src.is_16bit() {
+                    self.bus.write_16bit(address, self.registers.get(src));
+                } else {
+                    self.bus.write(address, self.registers.get_8bit(src));
+                }
+            }
+            (Opcode::Ld, OpcodeParameter::Register_Register(dst, src)) => {
+                self.registers.set(dst, self.registers.get(src));
+            }
+
+            (Opcode::Ldh, OpcodeParameter::U16_Register(src)) => {
+                let address = 0xFF00 | self.fetch_byte() as u16;
+                self.bus.write(address, self.registers.get_8bit(src));
+            }
+            (Opcode::Ldh, OpcodeParameter::Register_U16(dst)) => {
+                let address = 0xFF00 | self.fetch_byte() as u16;
+                self.registers.set(dst, self.bus.read(address) as u16);
+            }
+
+            (Opcode::Inc, OpcodeParameter::Register(reg)) => {
+                let value = self.registers.get(reg).wrapping_add(1);
+                self.registers.set(reg, value);
+                if reg.is_8bit() {
+                    self.set_inc_dec_flags(value as u8, false);
+                }
+            }
+            (Opcode::Dec, OpcodeParameter::Register(reg)) => {
+                let value = self.registers.get(reg).wrapping_sub(1);
+                self.registers.set(reg, value);
+                if reg.is_8bit() {
+                    self.set_inc_dec_flags(value as u8, true);
+                }
+            }
+
+            (Opcode::Add, OpcodeParameter::Register_Register(Register::A, src)) => {
+                let value = self.registers.get_8bit(src);
+                alu::alu_add(&mut self.registers, value);
+            }
+            (Opcode::Add, OpcodeParameter::Register_U8(Register::A)) => {
+                let value = self.fetch_byte();
+                alu::alu_add(&mut self.registers, value);
+            }
+            (Opcode::Sub, OpcodeParameter::Register_Register(Register::A, src)) => {
+                let value = self.registers.get_8bit(src);
+                alu::alu_sub(&mut self.registers, value);
+            }
+            (Opcode::Sub, OpcodeParameter::Register_U8(Register::A)) => {
+                let value = self.fetch_byte();
+                alu::alu_sub(&mut self.registers, value);
+            }
+            (Opcode::Cp, OpcodeParameter::Register_Register(Register::A, src)) => {
+                let value = self.registers.get_8bit(src);
+                alu::alu_cp(&mut self.registers, value);
+            }
+            (Opcode::Cp, OpcodeParameter::Register_U8(Register::A)) => {
+                let value = self.fetch_byte();
+                alu::alu_cp(&mut self.registers, value);
+            }
+
+            (Opcode::Jp, OpcodeParameter::U16) => {
+                let address = self.fetch_word();
+                self.registers.set_pc(address);
+            }
+            (Opcode::Jr, OpcodeParameter::I8) => {
+                let offset = self.fetch_i8();
+                let pc = self.registers.pc() as i32 + offset as i32;
+                self.registers.set_pc(pc as u16);
+            }
+            (Opcode::Call, OpcodeParameter::U16) => {
+                let address = self.fetch_word();
+                let return_address = self.registers.pc();
+                self.push(return_address);
+                self.registers.set_pc(address);
+            }
+            (Opcode::Ret, OpcodeParameter::None) => {
+                let address = self.pop();
+                self.registers.set_pc(address);
+            }
+
+            (Opcode::Rlca, OpcodeParameter::None) => {
+                let a = self.registers.get_8bit(Register::A);
+                let carry = a & 0x80 != 0;
+                let result = a.rotate_left(1);
+                self.registers.set(Register::A, result as u16);
+                self.set_non_cb_rotate_flags(carry);
+            }
+            (Opcode::Rrca, OpcodeParameter::None) => {
+                let a = self.registers.get_8bit(Register::A);
+                let carry = a & 0x01 != 0;
+                let result = a.rotate_right(1);
+                self.registers.set(Register::A, result as u16);
+                self.set_non_cb_rotate_flags(carry);
+            }
+            (Opcode::Rla, OpcodeParameter::None) => {
+                let a = self.registers.get_8bit(Register::A);
+                let old_carry = self.registers.get_flag(FlagRegister::Carry) as u8;
+                let carry = a & 0x80 != 0;
+                let result = (a << 1) | old_carry;
+                self.registers.set(Register::A, result as u16);
+                self.set_non_cb_rotate_flags(carry);
+            }
+            (Opcode::Rra, OpcodeParameter::None) => {
+                let a = self.registers.get_8bit(Register::A);
+                let old_carry = self.registers.get_flag(FlagRegister::Carry) as u8;
+                let carry = a & 0x01 != 0;
+                let result = (a >> 1) | (old_carry << 7);
+                self.registers.set(Register::A, result as u16);
+                self.set_non_cb_rotate_flags(carry);
+            }
+
+            (Opcode::Di, OpcodeParameter::None) => {
+                self.ime = false;
+                self.ime_scheduled = false;
+            }
+            (Opcode::Ei, OpcodeParameter::None) => {
+                self.ime_scheduled = true;
+            }
+            (Opcode::Reti, OpcodeParameter::None) => {
+                let address = self.pop();
+                self.registers.set_pc(address);
+                self.ime = true;
+            }
+            (Opcode::Halt, OpcodeParameter::None) => {
+                self.halted = true;
+            }
+
+            (opcode, parameter) => {
+                panic!("unhandled opcode/parameter combination: {opcode:?} {parameter:?}")
+            }
+        }
+    }
+
+    /// set_inc_dec_flags sets Zero/Subtract/HalfCarry the way INC/DEC do:
+    /// Carry is left untouched, unlike the ALU add/sub routines.
+    fn set_inc_dec_flags(&mut self, result: u8, is_dec: bool) {
+        self.registers.set_flag(FlagRegister::Zero, result == 0);
+        self.registers.set_flag(FlagRegister::Subtract, is_dec);
+        let half_carry = if is_dec {
+            result & 0x0F == 0x0F
+        } else {
+            result & 0x0F == 0x00
+        };
+        self.registers.set_flag(FlagRegister::HalfCarry, half_carry);
+    }
+
+    /// set_non_cb_rotate_flags applies the flag behavior shared by
+    /// RLCA/RRCA/RLA/RRA: Zero is always cleared (unlike their CB
+    /// counterparts, which set Zero from the result), Subtract and
+    /// HalfCarry are cleared, and Carry takes the rotated-out bit.
+    fn set_non_cb_rotate_flags(&mut self, carry: bool) {
+        self.registers.set_flag(FlagRegister::Zero, false);
+        self.registers.set_flag(FlagRegister::Subtract, false);
+        self.registers.set_flag(FlagRegister::HalfCarry, false);
+        self.registers.set_flag(FlagRegister::Carry, carry);
+    }
+
+    /// cb_operand_register maps a `CbOperand` to the `Register` it reads
+    /// from directly; `HLIndirect` has no register and is handled by the
+    /// caller via the bus instead.
+    fn cb_operand_register(operand: CbOperand) -> Register {
+        match operand {
+            CbOperand::B => Register::B,
+            CbOperand::C => Register::C,
+            CbOperand::D => Register::D,
+            CbOperand::E => Register::E,
+            CbOperand::H => Register::H,
+            CbOperand::L => Register::L,
+            CbOperand::A => Register::A,
+            CbOperand::HLIndirect => {
+                unreachable!("HLIndirect is handled separately via the bus")
+            }
+        }
+    }
+
+    fn read_cb_operand(&self, operand: CbOperand) -> u8 {
+        match operand {
+            CbOperand::HLIndirect => self.bus.read(self.registers.get(Register::HL)),
+            _ => self.registers.get_8bit(Self::cb_operand_register(operand)),
+        }
+    }
+
+    fn write_cb_operand(&mut self, operand: CbOperand, value: u8) {
+        match operand {
+            CbOperand::HLIndirect => {
+                let address = self.registers.get(Register::HL);
+                self.bus.write(address, value);
+            }
+            _ => self.registers.set(Self::cb_operand_register(operand), value as u16),
+        }
+    }
+
+    /// set_shift_flags applies the flag behavior shared by every CB
+    /// rotate/shift op: Zero comes from the result, Subtract and HalfCarry
+    /// are cleared, and Carry takes the shifted-out bit.
+    fn set_shift_flags(&mut self, result: u8, carry: bool) {
+        self.registers.set_flag(FlagRegister::Zero, result == 0);
+        self.registers.set_flag(FlagRegister::Subtract, false);
+        self.registers.set_flag(FlagRegister::HalfCarry, false);
+        self.registers.set_flag(FlagRegister::Carry, carry);
+    }
+
+    fn execute_cb(&mut self, op: CbOp, operand: CbOperand) {
+        let value = self.read_cb_operand(operand);
+
+        match op {
+            CbOp::Rlc => {
+                let carry = value & 0x80 != 0;
+                let result = value.rotate_left(1);
+                self.write_cb_operand(operand, result);
+                self.set_shift_flags(result, carry);
+            }
+            CbOp::Rrc => {
+                let carry = value & 0x01 != 0;
+                let result = value.rotate_right(1);
+                self.write_cb_operand(operand, result);
+                self.set_shift_flags(result, carry);
+            }
+            CbOp::Rl => {
+                let old_carry = self.registers.get_flag(FlagRegister::Carry) as u8;
+                let carry = value & 0x80 != 0;
+                let result = (value << 1) | old_carry;
+                self.write_cb_operand(operand, result);
+                self.set_shift_flags(result, carry);
+            }
+            CbOp::Rr => {
+                let old_carry = self.registers.get_flag(FlagRegister::Carry) as u8;
+                let carry = value & 0x01 != 0;
+                let result = (value >> 1) | (old_carry << 7);
+                self.write_cb_operand(operand, result);
+                self.set_shift_flags(result, carry);
+            }
+            CbOp::Sla => {
+                let carry = value & 0x80 != 0;
+                let result = value << 1;
+                self.write_cb_operand(operand, result);
+                self.set_shift_flags(result, carry);
+            }
+            CbOp::Sra => {
+                let carry = value & 0x01 != 0;
+                let result = (value >> 1) | (value & 0x80);
+                self.write_cb_operand(operand, result);
+                self.set_shift_flags(result, carry);
+            }
+            CbOp::Swap => {
+                let result = value.rotate_left(4);
+                self.write_cb_operand(operand, result);
+                self.registers.set_flag(FlagRegister::Zero, result == 0);
+                self.registers.set_flag(FlagRegister::Subtract, false);
+                self.registers.set_flag(FlagRegister::HalfCarry, false);
+                self.registers.set_flag(FlagRegister::Carry, false);
+            }
+            CbOp::Srl => {
+                let carry = value & 0x01 != 0;
+                let result = value >> 1;
+                self.write_cb_operand(operand, result);
+                self.set_shift_flags(result, carry);
+            }
+            CbOp::Bit(bit) => {
+                self.registers
+                    .set_flag(FlagRegister::Zero, value & (1 << bit) == 0);
+                self.registers.set_flag(FlagRegister::Subtract, false);
+                self.registers.set_flag(FlagRegister::HalfCarry, true);
+            }
+            CbOp::Res(bit) => {
+                self.write_cb_operand(operand, value & !(1 << bit));
+            }
+            CbOp::Set(bit) => {
+                self.write_cb_operand(operand, value | (1 << bit));
+            }
+        }
+    }
+}
+
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Work RAM is writable (ROM writes are ignored by the bus), so test
+    /// programs live at 0xC000 with PC pointed there before stepping.
+    const PROGRAM_START: u16 = 0xC000;
+
+    fn cpu_with_program(bytes: &[u8]) -> Cpu {
+        let mut cpu = Cpu::new();
+        cpu.registers.set_pc(PROGRAM_START);
+        for (i, byte) in bytes.iter().enumerate() {
+            cpu.bus.write(PROGRAM_START + i as u16, *byte);
+        }
+        cpu
+    }
+
+    #[test]
+    fn test_post_boot_seeds_registers_and_is_otherwise_fresh() {
+        let cpu = Cpu::post_boot();
+        assert_eq!(cpu.registers.pc(), 0x0100);
+        assert_eq!(cpu.registers.sp(), 0xFFFE);
+        assert_eq!(cpu.registers.af(), 0x01B0);
+        assert!(!cpu.ime);
+        assert!(!cpu.halted);
+    }
+
+    #[test]
+    fn test_nop_advances_pc_by_one() {
+        let mut cpu = cpu_with_program(&[0x00]);
+        cpu.step();
+        assert_eq!(cpu.registers.pc(), PROGRAM_START + 1);
+    }
+
+    #[test]
+    fn test_ld_register_u8() {
+        let mut cpu = cpu_with_program(&[0x3E, 0x42]); // LD A,u8
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0x42);
+        assert_eq!(cpu.registers.pc(), PROGRAM_START + 2);
+    }
+
+    #[test]
+    fn test_ld_register_u16() {
+        let mut cpu = cpu_with_program(&[0x21, 0xEF, 0xBE]); // LD HL,u16
+        cpu.step();
+        assert_eq!(cpu.registers.get(Register::HL), 0xBEEF);
+        assert_eq!(cpu.registers.pc(), PROGRAM_START + 3);
+    }
+
+    #[test]
+    fn test_ld_register_register() {
+        let mut cpu = cpu_with_program(&[0x78]); // LD A,B
+        cpu.registers.set(Register::B, 0x99);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0x99);
+    }
+
+    #[test]
+    fn test_ld_address_register() {
+        let mut cpu = cpu_with_program(&[0xEA, 0x00, 0xD0]); // LD (u16),A
+        cpu.registers.set(Register::A, 0x42);
+        cpu.step();
+        assert_eq!(cpu.bus.read(0xD000), 0x42);
+    }
+
+    #[test]
+    fn test_ld_address_register_round_trip() {
+        // LD (u16),A then LD A,(u16), clearing A before the read back so
+        // the test can't pass by accident.
+        let mut cpu = cpu_with_program(&[0xEA, 0x00, 0xD0, 0xFA, 0x00, 0xD0]);
+        cpu.registers.set(Register::A, 0x42);
+        cpu.step();
+        assert_eq!(cpu.bus.read(0xD000), 0x42);
+
+        cpu.registers.set(Register::A, 0x00);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0x42);
+    }
+
+    #[test]
+    fn test_ldh_write_and_read() {
+        let mut cpu = cpu_with_program(&[0xE0, 0x10, 0xF0, 0x10]); // LDH (u8),A then LDH A,(u8)
+        cpu.registers.set(Register::A, 0x7A);
+        cpu.step();
+        assert_eq!(cpu.bus.read(0xFF10), 0x7A);
+
+        cpu.registers.set(Register::A, 0x00);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0x7A);
+    }
+
+    #[test]
+    fn test_inc_8bit_sets_zero_and_half_carry() {
+        let mut cpu = cpu_with_program(&[0x3C]); // INC A
+        cpu.registers.set(Register::A, 0xFF);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0x00);
+        assert!(cpu.registers.zero());
+        assert!(cpu.registers.half_carry());
+        assert!(!cpu.registers.subtract());
+    }
+
+    #[test]
+    fn test_dec_8bit_sets_subtract_flag() {
+        let mut cpu = cpu_with_program(&[0x05]); // DEC B
+        cpu.registers.set(Register::B, 0x01);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::B), 0x00);
+        assert!(cpu.registers.zero());
+        assert!(cpu.registers.subtract());
+    }
+
+    #[test]
+    fn test_inc_16bit_does_not_touch_flags() {
+        let mut cpu = cpu_with_program(&[0x23]); // INC HL
+        cpu.registers.set(Register::HL, 0xFFFF);
+        cpu.registers.set_zero(true);
+        cpu.step();
+        assert_eq!(cpu.registers.get(Register::HL), 0x0000);
+        assert!(cpu.registers.zero()); // untouched, still true from before
+    }
+
+    #[test]
+    fn test_add_a_register() {
+        let mut cpu = cpu_with_program(&[0x80]); // ADD A,B
+        cpu.registers.set(Register::A, 0x0A);
+        cpu.registers.set(Register::B, 0x01);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0x0B);
+    }
+
+    #[test]
+    fn test_sub_a_u8() {
+        let mut cpu = cpu_with_program(&[0xD6, 0x01]); // SUB u8
+        cpu.registers.set(Register::A, 0x10);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0x0F);
+    }
+
+    #[test]
+    fn test_cp_a_register_does_not_change_a() {
+        let mut cpu = cpu_with_program(&[0xB8]); // CP B
+        cpu.registers.set(Register::A, 0x05);
+        cpu.registers.set(Register::B, 0x05);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0x05);
+        assert!(cpu.registers.zero());
+    }
+
+    #[test]
+    fn test_jp_sets_pc() {
+        let mut cpu = cpu_with_program(&[0xC3, 0x34, 0x12]); // JP u16
+        cpu.step();
+        assert_eq!(cpu.registers.pc(), 0x1234);
+    }
+
+    #[test]
+    fn test_jr_relative_forward_and_backward() {
+        let mut cpu = cpu_with_program(&[0x18, 0x05]); // JR +5
+        cpu.step();
+        assert_eq!(cpu.registers.pc(), PROGRAM_START + 2 + 5);
+
+        cpu.bus.write(cpu.registers.pc(), 0x18); // JR -10
+        cpu.bus.write(cpu.registers.pc() + 1, (-10i8) as u8);
+        let before = cpu.registers.pc();
+        cpu.step();
+        assert_eq!(cpu.registers.pc(), before + 2 - 10);
+    }
+
+    #[test]
+    fn test_call_and_ret_round_trip() {
+        let mut cpu = cpu_with_program(&[0xCD, 0x00, 0xD0]); // CALL 0xD000
+        cpu.registers.set_sp(0xFFFE);
+        let return_address = cpu.registers.pc() + 3;
+        cpu.step();
+        assert_eq!(cpu.registers.pc(), 0xD000);
+        assert_eq!(cpu.registers.sp(), 0xFFFC);
+
+        cpu.bus.write(0xD000, 0xC9); // RET
+        cpu.step();
+        assert_eq!(cpu.registers.pc(), return_address);
+        assert_eq!(cpu.registers.sp(), 0xFFFE);
+    }
+
+    #[test]
+    #[should_panic(expected = "unimplemented opcode")]
+    fn test_unimplemented_opcode_panics() {
+        let mut cpu = cpu_with_program(&[0xD3]);
+        cpu.step();
+    }
+
+    #[test]
+    fn test_rlca_clears_zero_even_when_result_is_zero() {
+        let mut cpu = cpu_with_program(&[0x07]); // RLCA
+        cpu.registers.set(Register::A, 0x00);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0x00);
+        assert!(!cpu.registers.zero()); // RLCA always clears Zero
+        assert!(!cpu.registers.carry());
+    }
+
+    #[test]
+    fn test_rlca_sets_carry_from_bit_7() {
+        let mut cpu = cpu_with_program(&[0x07]); // RLCA
+        cpu.registers.set(Register::A, 0x80);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0x01);
+        assert!(cpu.registers.carry());
+        assert!(!cpu.registers.zero());
+    }
+
+    #[test]
+    fn test_cb_rlc_sets_zero_from_result() {
+        let mut cpu = cpu_with_program(&[0xCB, 0x00]); // RLC B
+        cpu.registers.set(Register::B, 0x00);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::B), 0x00);
+        assert!(cpu.registers.zero()); // unlike RLCA, CB RLC does set Zero
+    }
+
+    #[test]
+    fn test_cb_rl_through_carry() {
+        let mut cpu = cpu_with_program(&[0xCB, 0x10]); // RL B
+        cpu.registers.set(Register::B, 0x80);
+        cpu.registers.set_carry(true);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::B), 0x01);
+        assert!(cpu.registers.carry());
+    }
+
+    #[test]
+    fn test_cb_swap_clears_carry() {
+        let mut cpu = cpu_with_program(&[0xCB, 0x37]); // SWAP A
+        cpu.registers.set(Register::A, 0x12);
+        cpu.registers.set_carry(true);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0x21);
+        assert!(!cpu.registers.carry());
+    }
+
+    #[test]
+    fn test_cb_bit_sets_zero_to_complement_of_bit() {
+        let mut cpu = cpu_with_program(&[0xCB, 0x40]); // BIT 0,B
+        cpu.registers.set(Register::B, 0x01);
+        cpu.registers.set_carry(true);
+        cpu.step();
+        assert!(!cpu.registers.zero()); // bit 0 is set, so Zero is cleared
+        assert!(cpu.registers.half_carry());
+        assert!(cpu.registers.carry()); // BIT leaves Carry untouched
+    }
+
+    #[test]
+    fn test_cb_res_and_set() {
+        let mut cpu = cpu_with_program(&[0xCB, 0x87, 0xCB, 0xC7]); // RES 0,A then SET 0,A
+        cpu.registers.set(Register::A, 0xFF);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0xFE);
+        cpu.step();
+        assert_eq!(cpu.registers.get_8bit(Register::A), 0xFF);
+    }
+
+    #[test]
+    fn test_cb_hl_indirect_reads_and_writes_through_bus() {
+        let mut cpu = cpu_with_program(&[0xCB, 0x06]); // RLC (HL)
+        cpu.registers.set(Register::HL, 0xD000);
+        cpu.bus.write(0xD000, 0x80);
+        cpu.step();
+        assert_eq!(cpu.bus.read(0xD000), 0x01);
+        assert!(cpu.registers.carry());
+    }
+
+    #[test]
+    fn test_di_clears_ime_immediately() {
+        let mut cpu = cpu_with_program(&[0xF3]); // DI
+        cpu.ime = true;
+        cpu.step();
+        assert!(!cpu.ime);
+    }
+
+    #[test]
+    fn test_ei_enables_ime_only_after_the_next_instruction() {
+        let mut cpu = cpu_with_program(&[0xFB, 0x00, 0x00]); // EI; NOP; NOP
+        cpu.step(); // EI itself: IME not yet enabled
+        assert!(!cpu.ime);
+        cpu.step(); // instruction following EI: still not enabled during it...
+        assert!(cpu.ime); // ...but enabled once it finishes
+    }
+
+    #[test]
+    fn test_reti_enables_ime_immediately_and_returns() {
+        let mut cpu = cpu_with_program(&[0xD9]); // RETI
+        cpu.registers.set_sp(0xFFFC);
+        cpu.bus.write_16bit(0xFFFC, 0xABCD);
+        cpu.step();
+        assert_eq!(cpu.registers.pc(), 0xABCD);
+        assert!(cpu.ime);
+    }
+
+    #[test]
+    fn test_halt_stops_stepping_until_interrupt_pending() {
+        let mut cpu = cpu_with_program(&[0x76]); // HALT
+        cpu.step();
+        assert!(cpu.halted);
+
+        let pc_before = cpu.registers.pc();
+        cpu.step(); // still halted, no enabled interrupt pending
+        assert!(cpu.halted);
+        assert_eq!(cpu.registers.pc(), pc_before);
+
+        cpu.bus.write(crate::interrupts::IE_ADDRESS, Interrupt::Timer.mask());
+        cpu.bus.request_interrupt(Interrupt::Timer);
+        cpu.step(); // now wakes, but IME is still false so it doesn't service it
+        assert!(!cpu.halted);
+    }
+
+    #[test]
+    fn test_halt_wakes_and_services_interrupt_when_ime_set() {
+        let mut cpu = cpu_with_program(&[0xFB, 0x76]); // EI; HALT
+        cpu.bus.write(crate::interrupts::IE_ADDRESS, Interrupt::Timer.mask());
+
+        cpu.step(); // EI: IME enables after the next instruction
+        cpu.step(); // HALT: IME now true, no interrupt pending yet
+        assert!(cpu.halted);
+
+        cpu.bus.request_interrupt(Interrupt::Timer);
+        cpu.step(); // services the interrupt instead of staying halted
+
+        assert!(!cpu.halted);
+        assert_eq!(cpu.registers.pc(), Interrupt::Timer.vector());
+    }
+
+    #[test]
+    fn test_service_interrupts_pushes_pc_and_jumps_to_vector() {
+        let mut cpu = cpu_with_program(&[0x00]); // NOP, never reached
+        cpu.registers.set_sp(0xFFFE);
+        cpu.ime = true;
+        cpu.bus.write(crate::interrupts::IE_ADDRESS, Interrupt::VBlank.mask());
+        cpu.bus.request_interrupt(Interrupt::VBlank);
+
+        let pc_before = cpu.registers.pc();
+        cpu.step();
+
+        assert_eq!(cpu.registers.pc(), Interrupt::VBlank.vector());
+        assert!(!cpu.ime);
+        assert!(!cpu.bus.interrupt_requested(Interrupt::VBlank));
+        assert_eq!(cpu.registers.sp(), 0xFFFC);
+        assert_eq!(cpu.bus.read_16bit(0xFFFC), pc_before);
+    }
+
+    #[test]
+    fn test_service_interrupts_respects_priority_order() {
+        let mut cpu = cpu_with_program(&[0x00]);
+        cpu.registers.set_sp(0xFFFE);
+        cpu.ime = true;
+        cpu.bus.write(
+            crate::interrupts::IE_ADDRESS,
+            Interrupt::Timer.mask() | Interrupt::VBlank.mask(),
+        );
+        cpu.bus.request_interrupt(Interrupt::Timer);
+        cpu.bus.request_interrupt(Interrupt::VBlank);
+
+        cpu.step();
+
+        assert_eq!(cpu.registers.pc(), Interrupt::VBlank.vector());
+        assert!(cpu.bus.interrupt_requested(Interrupt::Timer)); // untouched
+    }
+
+    #[test]
+    fn test_no_interrupt_serviced_when_ime_false() {
+        let mut cpu = cpu_with_program(&[0x00]); // NOP
+        cpu.ime = false;
+        cpu.bus.write(crate::interrupts::IE_ADDRESS, Interrupt::VBlank.mask());
+        cpu.bus.request_interrupt(Interrupt::VBlank);
+
+        cpu.step();
+
+        assert_eq!(cpu.registers.pc(), PROGRAM_START + 1); // ran the NOP instead
+    }
+}
@@ -0,0 +1,124 @@
+//! Interrupt flag bits shared by the memory-mapped IE (0xFFFF) and IF
+//! (0xFF0F) registers, plus the `Bus` helpers that read and write them.
+//! `Cpu` uses these to decide, once per step, whether to service an
+//! interrupt instead of executing the next opcode.
+
+use crate::bus::Bus;
+
+/// Interrupt Enable register address.
+pub const IE_ADDRESS: u16 = 0xFFFF;
+/// Interrupt Flag (request) register address.
+pub const IF_ADDRESS: u16 = 0xFF0F;
+
+/// Interrupt identifies one of the five DMG interrupt sources. Variants are
+/// declared in priority order, highest first, matching the fixed vectors
+/// the CPU jumps to when servicing one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interrupt {
+    VBlank,
+    LcdStat,
+    Timer,
+    Serial,
+    Joypad,
+}
+
+impl Interrupt {
+    /// ALL lists every interrupt in priority order, highest first.
+    pub const ALL: [Interrupt; 5] = [
+        Interrupt::VBlank,
+        Interrupt::LcdStat,
+        Interrupt::Timer,
+        Interrupt::Serial,
+        Interrupt::Joypad,
+    ];
+
+    /// bit is this interrupt's position in the IE/IF registers.
+    pub fn bit(&self) -> u8 {
+        match self {
+            Interrupt::VBlank => 0,
+            Interrupt::LcdStat => 1,
+            Interrupt::Timer => 2,
+            Interrupt::Serial => 3,
+            Interrupt::Joypad => 4,
+        }
+    }
+
+    /// mask is this interrupt's bitmask within the IE/IF registers.
+    pub fn mask(&self) -> u8 {
+        1 << self.bit()
+    }
+
+    /// vector is the fixed address the interrupt service routine jumps to.
+    pub fn vector(&self) -> u16 {
+        match self {
+            Interrupt::VBlank => 0x40,
+            Interrupt::LcdStat => 0x48,
+            Interrupt::Timer => 0x50,
+            Interrupt::Serial => 0x58,
+            Interrupt::Joypad => 0x60,
+        }
+    }
+}
+
+impl Bus {
+    pub fn interrupt_enabled(&self, interrupt: Interrupt) -> bool {
+        self.read(IE_ADDRESS) & interrupt.mask() != 0
+    }
+
+    pub fn interrupt_requested(&self, interrupt: Interrupt) -> bool {
+        self.read(IF_ADDRESS) & interrupt.mask() != 0
+    }
+
+    pub fn request_interrupt(&mut self, interrupt: Interrupt) {
+        let flags = self.read(IF_ADDRESS) | interrupt.mask();
+        self.write(IF_ADDRESS, flags);
+    }
+
+    pub fn clear_interrupt(&mut self, interrupt: Interrupt) {
+        let flags = self.read(IF_ADDRESS) & !interrupt.mask();
+        self.write(IF_ADDRESS, flags);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_and_read_interrupt_flag() {
+        let mut bus = Bus::new();
+        assert!(!bus.interrupt_requested(Interrupt::Timer));
+        bus.request_interrupt(Interrupt::Timer);
+        assert!(bus.interrupt_requested(Interrupt::Timer));
+        assert_eq!(bus.read(IF_ADDRESS), 0b0000_0100);
+    }
+
+    #[test]
+    fn test_clear_interrupt_leaves_others_set() {
+        let mut bus = Bus::new();
+        bus.request_interrupt(Interrupt::VBlank);
+        bus.request_interrupt(Interrupt::Joypad);
+        bus.clear_interrupt(Interrupt::VBlank);
+        assert!(!bus.interrupt_requested(Interrupt::VBlank));
+        assert!(bus.interrupt_requested(Interrupt::Joypad));
+    }
+
+    #[test]
+    fn test_interrupt_enable_register() {
+        let mut bus = Bus::new();
+        assert!(!bus.interrupt_enabled(Interrupt::Serial));
+        bus.write(IE_ADDRESS, Interrupt::Serial.mask());
+        assert!(bus.interrupt_enabled(Interrupt::Serial));
+    }
+
+    #[test]
+    fn test_vectors_and_priority_order() {
+        assert_eq!(Interrupt::VBlank.vector(), 0x40);
+        assert_eq!(Interrupt::LcdStat.vector(), 0x48);
+        assert_eq!(Interrupt::Timer.vector(), 0x50);
+        assert_eq!(Interrupt::Serial.vector(), 0x58);
+        assert_eq!(Interrupt::Joypad.vector(), 0x60);
+        assert_eq!(Interrupt::ALL[0], Interrupt::VBlank);
+        assert_eq!(Interrupt::ALL[4], Interrupt::Joypad);
+    }
+}
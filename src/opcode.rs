@@ -0,0 +1,339 @@
+//! Non-CB opcode decode table. `decode` maps a raw opcode byte to an
+//! `Opcode` mnemonic plus an `OpcodeParameter` tag describing the addressing
+//! mode, without reading any operand bytes itself — `Cpu::execute` reads
+//! immediates from the bus as each handler needs them. Tagging the addressing
+//! mode separately from the mnemonic means one execute match arm can cover
+//! every opcode that shares a shape (e.g. all seven `LD r,u8` opcodes).
+
+use crate::registers::Register;
+
+/// Opcode is the mnemonic family a decoded instruction belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Nop,
+    Ld,
+    Ldh,
+    Inc,
+    Dec,
+    Add,
+    Sub,
+    Cp,
+    Jp,
+    Jr,
+    Call,
+    Ret,
+    /// Rotate A left, bit 7 into both bit 0 and Carry. Unlike CB `RLC A`,
+    /// this always clears Zero.
+    Rlca,
+    /// Rotate A right, bit 0 into both bit 7 and Carry. Always clears Zero.
+    Rrca,
+    /// Rotate A left through Carry. Always clears Zero.
+    Rla,
+    /// Rotate A right through Carry. Always clears Zero.
+    Rra,
+    /// Disable interrupts (IME := false) immediately.
+    Di,
+    /// Enable interrupts (IME := true), but only after the instruction
+    /// following this one finishes executing.
+    Ei,
+    /// Pop PC off the stack and enable interrupts immediately (unlike EI,
+    /// with no one-instruction delay).
+    Reti,
+    /// Stop executing instructions until an enabled interrupt is pending.
+    Halt,
+}
+
+/// OpcodeParameter tags the addressing mode of a decoded instruction: which
+/// operands it has and where they come from. It carries no values, only
+/// shape; `Cpu::execute` reads the actual immediate bytes from the bus.
+///
+/// `Ld`/`Ldh` share the `U16_Register`/`Register_U16` shapes: a `Ld` fetches
+/// a 2-byte immediate address, an `Ldh` fetches a single byte and widens it
+/// to `0xFF00 | byte`. The shape is the same; only the opcode decides the
+/// immediate width.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpcodeParameter {
+    /// No operands: NOP, RET.
+    None,
+    /// A plain 16-bit immediate with no register operand: JP u16, CALL u16.
+    U16,
+    /// A plain signed 8-bit immediate with no register operand: JR i8.
+    I8,
+    /// A single register operand: INC r, DEC r (8-bit or 16-bit).
+    Register(Register),
+    /// Register destination, 8-bit immediate source: LD r,u8.
+    Register_U8(Register),
+    /// Register destination, 16-bit immediate source: LD rr,u16.
+    Register_U16(Register),
+    /// Register destination, byte at a 16-bit immediate address source:
+    /// LD A,(u16). Distinct from `Register_U16` because the immediate here
+    /// is an address to dereference, not the value to load.
+    Register_U16Indirect(Register),
+    /// Register destination, signed 8-bit immediate source: ADD SP,i8.
+    Register_I8(Register),
+    /// 16-bit address destination (immediate, or zero-page for Ldh),
+    /// register source: LD (u16),A / LD (u16),SP / LDH (u8),A.
+    U16_Register(Register),
+    /// Destination register, source register pair plus signed 8-bit
+    /// immediate: LD HL,SP+i8.
+    Register_RegisterPlusI8(Register, Register),
+    /// Destination register, source register: LD r,r'; ADD/SUB/CP A,r.
+    Register_Register(Register, Register),
+}
+
+/// decode maps a raw opcode byte to its `Opcode`/`OpcodeParameter` pair, or
+/// `None` if the byte isn't implemented yet.
+pub fn decode(byte: u8) -> Option<(Opcode, OpcodeParameter)> {
+    use Opcode::*;
+    use OpcodeParameter as P;
+    use Register::*;
+
+    match byte {
+        0x00 => Some((Nop, P::None)),
+        0x07 => Some((Rlca, P::None)),
+        0x0F => Some((Rrca, P::None)),
+        0x17 => Some((Rla, P::None)),
+        0x1F => Some((Rra, P::None)),
+        0x76 => Some((Halt, P::None)),
+        0xF3 => Some((Di, P::None)),
+        0xFB => Some((Ei, P::None)),
+        0xD9 => Some((Reti, P::None)),
+
+        // LD r,u8
+        0x06 => Some((Ld, P::Register_U8(B))),
+        0x0E => Some((Ld, P::Register_U8(C))),
+        0x16 => Some((Ld, P::Register_U8(D))),
+        0x1E => Some((Ld, P::Register_U8(E))),
+        0x26 => Some((Ld, P::Register_U8(H))),
+        0x2E => Some((Ld, P::Register_U8(L))),
+        0x3E => Some((Ld, P::Register_U8(A))),
+
+        // LD rr,u16
+        0x01 => Some((Ld, P::Register_U16(BC))),
+        0x11 => Some((Ld, P::Register_U16(DE))),
+        0x21 => Some((Ld, P::Register_U16(HL))),
+        0x31 => Some((Ld, P::Register_U16(SP))),
+
+        // LD (u16),SP / LD (u16),A / LD A,(u16)
+        0x08 => Some((Ld, P::U16_Register(SP))),
+        0xEA => Some((Ld, P::U16_Register(A))),
+        0xFA => Some((Ld, P::Register_U16Indirect(A))),
+
+        // LDH (u8),A / LDH A,(u8)
+        0xE0 => Some((Ldh, P::U16_Register(A))),
+        0xF0 => Some((Ldh, P::Register_U16(A))),
+
+        // LD r,r' (register-to-register moves only; (HL) indirect forms
+        // are left for a later pass)
+        0x40 => Some((Ld, P::Register_Register(B, B))),
+        0x41 => Some((Ld, P::Register_Register(B, C))),
+        0x42 => Some((Ld, P::Register_Register(B, D))),
+        0x43 => Some((Ld, P::Register_Register(B, E))),
+        0x44 => Some((Ld, P::Register_Register(B, H))),
+        0x45 => Some((Ld, P::Register_Register(B, L))),
+        0x47 => Some((Ld, P::Register_Register(B, A))),
+        0x48 => Some((Ld, P::Register_Register(C, B))),
+        0x49 => Some((Ld, P::Register_Register(C, C))),
+        0x4A => Some((Ld, P::Register_Register(C, D))),
+        0x4B => Some((Ld, P::Register_Register(C, E))),
+        0x4C => Some((Ld, P::Register_Register(C, H))),
+        0x4D => Some((Ld, P::Register_Register(C, L))),
+        0x4F => Some((Ld, P::Register_Register(C, A))),
+        0x78 => Some((Ld, P::Register_Register(A, B))),
+        0x79 => Some((Ld, P::Register_Register(A, C))),
+        0x7A => Some((Ld, P::Register_Register(A, D))),
+        0x7B => Some((Ld, P::Register_Register(A, E))),
+        0x7C => Some((Ld, P::Register_Register(A, H))),
+        0x7D => Some((Ld, P::Register_Register(A, L))),
+        0x7F => Some((Ld, P::Register_Register(A, A))),
+
+        // INC/DEC r (8-bit)
+        0x04 => Some((Inc, P::Register(B))),
+        0x0C => Some((Inc, P::Register(C))),
+        0x14 => Some((Inc, P::Register(D))),
+        0x1C => Some((Inc, P::Register(E))),
+        0x24 => Some((Inc, P::Register(H))),
+        0x2C => Some((Inc, P::Register(L))),
+        0x3C => Some((Inc, P::Register(A))),
+        0x05 => Some((Dec, P::Register(B))),
+        0x0D => Some((Dec, P::Register(C))),
+        0x15 => Some((Dec, P::Register(D))),
+        0x1D => Some((Dec, P::Register(E))),
+        0x25 => Some((Dec, P::Register(H))),
+        0x2D => Some((Dec, P::Register(L))),
+        0x3D => Some((Dec, P::Register(A))),
+
+        // INC/DEC rr (16-bit)
+        0x03 => Some((Inc, P::Register(BC))),
+        0x13 => Some((Inc, P::Register(DE))),
+        0x23 => Some((Inc, P::Register(HL))),
+        0x33 => Some((Inc, P::Register(SP))),
+        0x0B => Some((Dec, P::Register(BC))),
+        0x1B => Some((Dec, P::Register(DE))),
+        0x2B => Some((Dec, P::Register(HL))),
+        0x3B => Some((Dec, P::Register(SP))),
+
+        // ADD A,r / ADD A,u8
+        0x80 => Some((Add, P::Register_Register(A, B))),
+        0x81 => Some((Add, P::Register_Register(A, C))),
+        0x82 => Some((Add, P::Register_Register(A, D))),
+        0x83 => Some((Add, P::Register_Register(A, E))),
+        0x84 => Some((Add, P::Register_Register(A, H))),
+        0x85 => Some((Add, P::Register_Register(A, L))),
+        0x87 => Some((Add, P::Register_Register(A, A))),
+        0xC6 => Some((Add, P::Register_U8(A))),
+
+        // SUB r / SUB u8
+        0x90 => Some((Sub, P::Register_Register(A, B))),
+        0x91 => Some((Sub, P::Register_Register(A, C))),
+        0x92 => Some((Sub, P::Register_Register(A, D))),
+        0x93 => Some((Sub, P::Register_Register(A, E))),
+        0x94 => Some((Sub, P::Register_Register(A, H))),
+        0x95 => Some((Sub, P::Register_Register(A, L))),
+        0x97 => Some((Sub, P::Register_Register(A, A))),
+        0xD6 => Some((Sub, P::Register_U8(A))),
+
+        // CP r / CP u8
+        0xB8 => Some((Cp, P::Register_Register(A, B))),
+        0xB9 => Some((Cp, P::Register_Register(A, C))),
+        0xBA => Some((Cp, P::Register_Register(A, D))),
+        0xBB => Some((Cp, P::Register_Register(A, E))),
+        0xBC => Some((Cp, P::Register_Register(A, H))),
+        0xBD => Some((Cp, P::Register_Register(A, L))),
+        0xBF => Some((Cp, P::Register_Register(A, A))),
+        0xFE => Some((Cp, P::Register_U8(A))),
+
+        // Control flow
+        0xC3 => Some((Jp, P::U16)),
+        0x18 => Some((Jr, P::I8)),
+        0xCD => Some((Call, P::U16)),
+        0xC9 => Some((Ret, P::None)),
+
+        _ => Option::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_nop() {
+        assert_eq!(decode(0x00), Some((Opcode::Nop, OpcodeParameter::None)));
+    }
+
+    #[test]
+    fn test_decode_ld_register_u8() {
+        assert_eq!(
+            decode(0x3E),
+            Some((Opcode::Ld, OpcodeParameter::Register_U8(Register::A)))
+        );
+    }
+
+    #[test]
+    fn test_decode_ld_register_u16() {
+        assert_eq!(
+            decode(0x21),
+            Some((Opcode::Ld, OpcodeParameter::Register_U16(Register::HL)))
+        );
+    }
+
+    #[test]
+    fn test_decode_ld_address_forms() {
+        assert_eq!(
+            decode(0xEA),
+            Some((Opcode::Ld, OpcodeParameter::U16_Register(Register::A)))
+        );
+        assert_eq!(
+            decode(0xFA),
+            Some((
+                Opcode::Ld,
+                OpcodeParameter::Register_U16Indirect(Register::A)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_decode_ld_register_register() {
+        assert_eq!(
+            decode(0x78),
+            Some((
+                Opcode::Ld,
+                OpcodeParameter::Register_Register(Register::A, Register::B)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_decode_inc_dec() {
+        assert_eq!(
+            decode(0x04),
+            Some((Opcode::Inc, OpcodeParameter::Register(Register::B)))
+        );
+        assert_eq!(
+            decode(0x0B),
+            Some((Opcode::Dec, OpcodeParameter::Register(Register::BC)))
+        );
+    }
+
+    #[test]
+    fn test_decode_alu_family() {
+        assert_eq!(
+            decode(0x80),
+            Some((
+                Opcode::Add,
+                OpcodeParameter::Register_Register(Register::A, Register::B)
+            ))
+        );
+        assert_eq!(
+            decode(0xC6),
+            Some((Opcode::Add, OpcodeParameter::Register_U8(Register::A)))
+        );
+        assert_eq!(
+            decode(0xFE),
+            Some((Opcode::Cp, OpcodeParameter::Register_U8(Register::A)))
+        );
+    }
+
+    #[test]
+    fn test_decode_control_flow() {
+        assert_eq!(decode(0xC3), Some((Opcode::Jp, OpcodeParameter::U16)));
+        assert_eq!(decode(0x18), Some((Opcode::Jr, OpcodeParameter::I8)));
+        assert_eq!(decode(0xCD), Some((Opcode::Call, OpcodeParameter::U16)));
+        assert_eq!(decode(0xC9), Some((Opcode::Ret, OpcodeParameter::None)));
+    }
+
+    #[test]
+    fn test_decode_ldh() {
+        assert_eq!(
+            decode(0xE0),
+            Some((Opcode::Ldh, OpcodeParameter::U16_Register(Register::A)))
+        );
+        assert_eq!(
+            decode(0xF0),
+            Some((Opcode::Ldh, OpcodeParameter::Register_U16(Register::A)))
+        );
+    }
+
+    #[test]
+    fn test_decode_non_cb_rotates() {
+        assert_eq!(decode(0x07), Some((Opcode::Rlca, OpcodeParameter::None)));
+        assert_eq!(decode(0x0F), Some((Opcode::Rrca, OpcodeParameter::None)));
+        assert_eq!(decode(0x17), Some((Opcode::Rla, OpcodeParameter::None)));
+        assert_eq!(decode(0x1F), Some((Opcode::Rra, OpcodeParameter::None)));
+    }
+
+    #[test]
+    fn test_decode_interrupt_control() {
+        assert_eq!(decode(0xF3), Some((Opcode::Di, OpcodeParameter::None)));
+        assert_eq!(decode(0xFB), Some((Opcode::Ei, OpcodeParameter::None)));
+        assert_eq!(decode(0xD9), Some((Opcode::Reti, OpcodeParameter::None)));
+        assert_eq!(decode(0x76), Some((Opcode::Halt, OpcodeParameter::None)));
+    }
+
+    #[test]
+    fn test_decode_unimplemented_opcode_returns_none() {
+        assert_eq!(decode(0xD3), None);
+    }
+}
@@ -0,0 +1,568 @@
+// Define CPU flag constants using bitwise representation
+// These constants represent individual bits in a status register.
+const FLAG_ZERO: u8 = 0b1000_0000; // Bit 7
+const FLAG_SUBTRACT: u8 = 0b0100_0000; // Bit 6
+const FLAG_HALF_CARRY: u8 = 0b0010_0000; // Bit 5
+const FLAG_CARRY: u8 = 0b0001_0000; // Bit 4
+
+/// Identifies a single addressable register, 8-bit or 16-bit, on the `Registers` struct.
+/// This lets instruction handlers take a `Register` operand and dispatch against
+/// `Registers::get`/`set` uniformly instead of matching on the opcode's specific register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    A,
+    F,
+    B,
+    C,
+    D,
+    E,
+    H,
+    L,
+    BC,
+    DE,
+    HL,
+    SP,
+    PC,
+}
+
+impl Register {
+    /// is_8bit returns true for the single-byte registers (A, F, B, C, D, E, H, L)
+    pub fn is_8bit(&self) -> bool {
+        matches!(
+            self,
+            Register::A
+                | Register::F
+                | Register::B
+                | Register::C
+                | Register::D
+                | Register::E
+                | Register::H
+                | Register::L
+        )
+    }
+
+    /// is_16bit returns true for the register pairs and special purpose registers (BC, DE, HL, SP, PC)
+    pub fn is_16bit(&self) -> bool {
+        !self.is_8bit()
+    }
+}
+
+/// Identifies a single flag bit in the F register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagRegister {
+    Zero,
+    Subtract,
+    HalfCarry,
+    Carry,
+}
+
+/// Represents the CPU registers of a simple 8-bit architecture.
+/// Contains both 8-bit general purpose registers and 16-bit special purpose registers.
+/// - A: Accumulator - Used for arithmetic and logic operations
+/// - F: Flags - Used to store the status flags resulting from operations
+/// - B, C: General-purpose - Used together as a 16-bit register pair (BC) often used as a counter
+/// - D, E: General-purpose - Used together as a 16-bit register pair (DE) often used as a pointer
+/// - H, L: Used together as a 16-bit register pair (HL) often used for indirect addressing
+/// - SP: Stack Pointer
+/// - PC: Program Counter
+#[derive(Debug)]
+pub struct Registers {
+    // 8-bit general purpose registers
+    /// Accumulator - The primary register for arithmetic and logic operations
+    a: u8,
+    /// Flags - Stores the status flags resulting from operations
+    f: u8,
+    /// The high byte of the BC pair.
+    b: u8,
+    /// The low byte of the BC pair.
+    c: u8,
+    /// The high byte of the DE pair.
+    d: u8,
+    /// The low byte of the DE pair.
+    e: u8,
+    /// The high byte of the HL pair.
+    h: u8,
+    /// The low byte of the HL pair.
+    l: u8,
+    // 16-bit special purpose registers
+    /// Stack Pointer
+    sp: u16,
+    /// Program Counter
+    pc: u16,
+}
+
+impl Registers {
+    /// new initializes all registers to zero
+    pub fn new() -> Self {
+        Registers {
+            a: 0,
+            f: 0,
+            b: 0,
+            c: 0,
+            d: 0,
+            e: 0,
+            h: 0,
+            l: 0,
+            sp: 0,
+            pc: 0,
+        }
+    }
+
+    /// get reads any register, 8-bit or 16-bit, widened to a `u16`.
+    pub fn get(&self, reg: Register) -> u16 {
+        match reg {
+            Register::A => self.a as u16,
+            Register::F => self.f as u16,
+            Register::B => self.b as u16,
+            Register::C => self.c as u16,
+            Register::D => self.d as u16,
+            Register::E => self.e as u16,
+            Register::H => self.h as u16,
+            Register::L => self.l as u16,
+            Register::BC => self.bc(),
+            Register::DE => self.de(),
+            Register::HL => self.hl(),
+            Register::SP => self.sp,
+            Register::PC => self.pc,
+        }
+    }
+
+    /// get_8bit reads an 8-bit register, narrowing 16-bit operands via
+    /// `to_be_bytes()[1]` (i.e. their low byte). Never panics; given a
+    /// register pair, it silently returns the low byte rather than the full
+    /// 16-bit value, so callers should check `Register::is_8bit()` first.
+    pub fn get_8bit(&self, reg: Register) -> u8 {
+        self.get(reg).to_be_bytes()[1]
+    }
+
+    /// set writes any register, 8-bit or 16-bit. 8-bit writes take the low byte of `val`.
+    /// Writes to F always mask the lower nibble to 0, same as `set_af`.
+    pub fn set(&mut self, reg: Register, val: u16) {
+        let byte = val.to_be_bytes()[1];
+        match reg {
+            Register::A => self.a = byte,
+            Register::F => self.f = byte & 0xF0,
+            Register::B => self.b = byte,
+            Register::C => self.c = byte,
+            Register::D => self.d = byte,
+            Register::E => self.e = byte,
+            Register::H => self.h = byte,
+            Register::L => self.l = byte,
+            Register::BC => self.set_bc(val),
+            Register::DE => self.set_de(val),
+            Register::HL => self.set_hl(val),
+            Register::SP => self.sp = val,
+            Register::PC => self.pc = val,
+        }
+    }
+
+    /// get_flag reads a single flag bit from F.
+    pub fn get_flag(&self, flag: FlagRegister) -> bool {
+        match flag {
+            FlagRegister::Zero => self.zero(),
+            FlagRegister::Subtract => self.subtract(),
+            FlagRegister::HalfCarry => self.half_carry(),
+            FlagRegister::Carry => self.carry(),
+        }
+    }
+
+    /// set_flag writes a single flag bit in F, leaving the others untouched.
+    pub fn set_flag(&mut self, flag: FlagRegister, value: bool) {
+        match flag {
+            FlagRegister::Zero => self.set_zero(value),
+            FlagRegister::Subtract => self.set_subtract(value),
+            FlagRegister::HalfCarry => self.set_half_carry(value),
+            FlagRegister::Carry => self.set_carry(value),
+        }
+    }
+
+    /// post_boot initializes registers to the state the DMG boot ROM leaves
+    /// them in just before handing control to cartridge code at 0x0100: AF =
+    /// 0x01B0, BC = 0x0013, DE = 0x00D8, HL = 0x014D, SP = 0xFFFE, PC =
+    /// 0x0100. Use this (instead of `new`, which is an all-zero reset) when
+    /// running a ROM without actually executing the boot ROM first.
+    pub fn post_boot() -> Self {
+        let mut registers = Registers::new();
+        registers.set_af(0x01B0);
+        registers.set_bc(0x0013);
+        registers.set_de(0x00D8);
+        registers.set_hl(0x014D);
+        registers.set_sp(0xFFFE);
+        registers.set_pc(0x0100);
+        registers
+    }
+
+    pub fn sp(&self) -> u16 {
+        self.sp
+    }
+
+    pub fn set_sp(&mut self, value: u16) {
+        self.sp = value;
+    }
+
+    pub fn pc(&self) -> u16 {
+        self.pc
+    }
+
+    pub fn set_pc(&mut self, value: u16) {
+        self.pc = value;
+    }
+
+    pub fn af(&self) -> u16 {
+        ((self.a as u16) << 8) | (self.f as u16)
+    }
+
+    /// set_af sets the AF register pair
+    /// Writes the high byte to A and the low byte to F
+    /// When setting the F register, the lower 4 bits are masked to 0
+    pub fn set_af(&mut self, value: u16) {
+        self.a = (value >> 8) as u8;
+        self.f = (value as u8) & 0xF0;
+    }
+
+    pub fn bc(&self) -> u16 {
+        ((self.b as u16) << 8) | (self.c as u16)
+    }
+
+    pub fn set_bc(&mut self, value: u16) {
+        self.b = (value >> 8) as u8;
+        self.c = value as u8;
+    }
+
+    pub fn de(&self) -> u16 {
+        ((self.d as u16) << 8) | (self.e as u16)
+    }
+
+    pub fn set_de(&mut self, value: u16) {
+        self.d = (value >> 8) as u8;
+        self.e = value as u8;
+    }
+
+    pub fn hl(&self) -> u16 {
+        ((self.h as u16) << 8) | (self.l as u16)
+    }
+
+    pub fn set_hl(&mut self, value: u16) {
+        self.h = (value >> 8) as u8;
+        self.l = value as u8;
+    }
+
+    pub fn zero(&self) -> bool {
+        (self.f & FLAG_ZERO) != 0
+    }
+
+    pub fn set_zero(&mut self, value: bool) {
+        if value {
+            self.f |= FLAG_ZERO;
+        } else {
+            self.f &= !FLAG_ZERO;
+        }
+    }
+
+    pub fn subtract(&self) -> bool {
+        (self.f & FLAG_SUBTRACT) != 0
+    }
+
+    pub fn set_subtract(&mut self, value: bool) {
+        if value {
+            self.f |= FLAG_SUBTRACT;
+        } else {
+            self.f &= !FLAG_SUBTRACT;
+        }
+    }
+
+    pub fn half_carry(&self) -> bool {
+        (self.f & FLAG_HALF_CARRY) != 0
+    }
+
+    pub fn set_half_carry(&mut self, value: bool) {
+        if value {
+            self.f |= FLAG_HALF_CARRY;
+        } else {
+            self.f &= !FLAG_HALF_CARRY;
+        }
+    }
+
+    pub fn carry(&self) -> bool {
+        (self.f & FLAG_CARRY) != 0
+    }
+
+    pub fn set_carry(&mut self, value: bool) {
+        if value {
+            self.f |= FLAG_CARRY;
+        } else {
+            self.f &= !FLAG_CARRY;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registers_initialization() {
+        let registers = Registers::new();
+        assert_eq!(registers.a, 0);
+        assert_eq!(registers.f, 0);
+        assert_eq!(registers.b, 0);
+        assert_eq!(registers.c, 0);
+        assert_eq!(registers.d, 0);
+        assert_eq!(registers.e, 0);
+        assert_eq!(registers.h, 0);
+        assert_eq!(registers.l, 0);
+        assert_eq!(registers.sp, 0);
+        assert_eq!(registers.pc, 0);
+    }
+
+    #[test]
+    fn test_16bit_register_pair_af() {
+        let mut registers = Registers::new();
+        registers.a = 0x12;
+        registers.f = 0x30;
+        assert_eq!(registers.af(), 0x1230);
+    }
+
+    #[test]
+    fn test_16bit_register_pair_bc() {
+        let mut registers = Registers::new();
+        registers.b = 0xAB;
+        registers.c = 0xCD;
+        assert_eq!(registers.bc(), 0xABCD);
+    }
+
+    #[test]
+    fn test_16bit_register_pair_de() {
+        let mut registers = Registers::new();
+        registers.d = 0x11;
+        registers.e = 0x22;
+        assert_eq!(registers.de(), 0x1122);
+    }
+
+    #[test]
+    fn test_16bit_register_pair_hl() {
+        let mut registers = Registers::new();
+        registers.h = 0xFF;
+        registers.l = 0x00;
+        assert_eq!(registers.hl(), 0xFF00);
+    }
+
+    #[test]
+    fn test_set_16bit_register_pair_af() {
+        let mut registers = Registers::new();
+        registers.set_af(0x34F0); // Only upper 4 bits of F should be set
+        assert_eq!(registers.a, 0x34);
+        assert_eq!(registers.f, 0xF0); //Will always mask lower 4 bits to 0
+    }
+
+    #[test]
+    fn test_set_af_masks_lower_4_bits() {
+        let mut registers = Registers::new();
+        registers.set_af(0x12FF); // Try to set all bits in F. The lower 4 should be masked.
+        assert_eq!(registers.a, 0x12);
+        assert_eq!(registers.f, 0xF0); // Lower 4 bits should be masked to 0
+    }
+
+    #[test]
+    fn test_set_16bit_register_pair_bc() {
+        let mut registers = Registers::new();
+        registers.set_bc(0x1234);
+        assert_eq!(registers.b, 0x12);
+        assert_eq!(registers.c, 0x34);
+        assert_eq!(registers.bc(), 0x1234);
+    }
+
+    #[test]
+    fn test_set_16bit_register_pair_de() {
+        let mut registers = Registers::new();
+        registers.set_de(0x5678);
+        assert_eq!(registers.d, 0x56);
+        assert_eq!(registers.e, 0x78);
+    }
+
+    #[test]
+    fn test_set_16bit_register_pair_hl() {
+        let mut registers = Registers::new();
+        registers.set_hl(0xABCD);
+        assert_eq!(registers.h, 0xAB);
+        assert_eq!(registers.l, 0xCD);
+    }
+
+    #[test]
+    fn test_zero_flag_getter_setter() {
+        let mut registers = Registers::new();
+        assert_eq!(registers.zero(), false);
+        registers.set_zero(true);
+        assert_eq!(registers.zero(), true);
+        assert_eq!(registers.f, 0b1000_0000);
+        registers.set_zero(false);
+        assert_eq!(registers.zero(), false);
+    }
+
+    #[test]
+    fn test_subtract_flag_getter_and_setter() {
+        let mut registers = Registers::new();
+        assert_eq!(registers.subtract(), false);
+        registers.set_subtract(true);
+        assert_eq!(registers.subtract(), true);
+        assert_eq!(registers.f, 0b0100_0000);
+        registers.set_subtract(false);
+        assert_eq!(registers.subtract(), false);
+    }
+
+    #[test]
+    fn test_half_carry_flag_getter_and_setter() {
+        let mut registers = Registers::new();
+        assert_eq!(registers.half_carry(), false);
+        registers.set_half_carry(true);
+        assert_eq!(registers.half_carry(), true);
+        assert_eq!(registers.f, 0b0010_0000);
+        registers.set_half_carry(false);
+        assert_eq!(registers.half_carry(), false);
+    }
+
+    #[test]
+    fn test_carry_flag_getter_and_setter() {
+        let mut registers = Registers::new();
+        assert_eq!(registers.carry(), false);
+        registers.set_carry(true);
+        assert_eq!(registers.carry(), true);
+        assert_eq!(registers.f, 0b0001_0000);
+        registers.set_carry(false);
+        assert_eq!(registers.carry(), false);
+    }
+
+    #[test]
+    fn test_multiple_flag_indpendence() {
+        // test that setting one flag does not affect others
+        let mut registers = Registers::new();
+
+        // set both zero and carry flags
+        registers.set_zero(true);
+        registers.set_carry(true);
+        assert_eq!(registers.f, 0b1001_0000);
+
+        // clear zero flag
+        registers.set_zero(false);
+        assert_eq!(registers.zero(), false);
+        assert_eq!(registers.carry(), true);
+        assert_eq!(registers.f, 0b0001_0000);
+
+        // set subtract flag and make sure carry is still set
+        registers.set_subtract(true);
+        assert_eq!(registers.carry(), true);
+        assert_eq!(registers.subtract(), true);
+        assert_eq!(registers.f, 0b0101_0000);
+    }
+
+    #[test]
+    fn test_all_flags_set_and_clear() {
+        let mut registers = Registers::new();
+
+        //setting all flags
+        registers.set_zero(true);
+        registers.set_subtract(true);
+        registers.set_half_carry(true);
+        registers.set_carry(true);
+
+        assert_eq!(registers.f, 0b1111_0000);
+        assert!(registers.zero());
+        assert!(registers.subtract());
+        assert!(registers.half_carry());
+        assert!(registers.carry());
+
+        //clearing all flags
+        registers.set_zero(false);
+        registers.set_subtract(false);
+        registers.set_half_carry(false);
+        registers.set_carry(false);
+
+        assert_eq!(registers.f, 0b0000_0000);
+        assert!(!registers.zero());
+        assert!(!registers.subtract());
+        assert!(!registers.half_carry());
+        assert!(!registers.carry());
+    }
+
+    #[test]
+    fn test_read_multiple_flags() {
+        let mut registers = Registers::new();
+
+        // Set zero and half-carry flags
+        registers.set_zero(true);
+        registers.set_half_carry(true);
+
+        assert!(registers.zero());
+        assert!(!registers.subtract());
+        assert!(registers.half_carry());
+        assert!(!registers.carry());
+    }
+
+    #[test]
+    fn test_get_8bit_registers() {
+        let mut registers = Registers::new();
+        registers.a = 0x12;
+        registers.b = 0x34;
+        assert_eq!(registers.get_8bit(Register::A), 0x12);
+        assert_eq!(registers.get_8bit(Register::B), 0x34);
+    }
+
+    #[test]
+    fn test_get_16bit_registers() {
+        let mut registers = Registers::new();
+        registers.set_bc(0xABCD);
+        assert_eq!(registers.get(Register::BC), 0xABCD);
+    }
+
+    #[test]
+    fn test_set_register_generic() {
+        let mut registers = Registers::new();
+        registers.set(Register::A, 0x42);
+        assert_eq!(registers.a, 0x42);
+        registers.set(Register::HL, 0xBEEF);
+        assert_eq!(registers.hl(), 0xBEEF);
+    }
+
+    #[test]
+    fn test_set_f_register_masks_lower_nibble() {
+        let mut registers = Registers::new();
+        registers.set(Register::F, 0xFF);
+        assert_eq!(registers.f, 0xF0);
+    }
+
+    #[test]
+    fn test_get_and_set_flag() {
+        let mut registers = Registers::new();
+        registers.set_flag(FlagRegister::Carry, true);
+        assert!(registers.get_flag(FlagRegister::Carry));
+        assert!(!registers.get_flag(FlagRegister::Zero));
+    }
+
+    #[test]
+    fn test_post_boot_state() {
+        let registers = Registers::post_boot();
+        assert_eq!(registers.af(), 0x01B0);
+        assert_eq!(registers.bc(), 0x0013);
+        assert_eq!(registers.de(), 0x00D8);
+        assert_eq!(registers.hl(), 0x014D);
+        assert_eq!(registers.sp(), 0xFFFE);
+        assert_eq!(registers.pc(), 0x0100);
+    }
+
+    #[test]
+    fn test_post_boot_f_is_b0() {
+        // Post-boot F is 0xB0: Zero, HalfCarry and Carry set, Subtract
+        // clear. Its lower nibble is already clear, so set_af's mask leaves
+        // it unchanged.
+        let registers = Registers::post_boot();
+        assert_eq!(registers.f, 0xB0);
+    }
+
+    #[test]
+    fn test_register_is_8bit_and_16bit() {
+        assert!(Register::A.is_8bit());
+        assert!(!Register::A.is_16bit());
+        assert!(Register::HL.is_16bit());
+        assert!(!Register::HL.is_8bit());
+    }
+}